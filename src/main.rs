@@ -1,10 +1,12 @@
 use std::{
+    collections::HashSet,
     env, fs,
-    io::{Read, Write},
-    path::PathBuf,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process,
 };
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use toml;
 
 #[derive(Debug, Clone)]
@@ -21,165 +23,315 @@ struct JavaLineConfig {
     name: String,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectConfig {
+    name: String,
+    version: String,
+    group: String,
+    source_dir: String,
+    main_class: String,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+// A scheme prefix on a name passed to `add`, stripped the way package managers parse
+// URI-like source prefixes (e.g. `sys:` for vendored packages, `local:` for local ones)
+enum PackageScheme {
+    Sys,
+    Local,
+}
+
+fn strip_scheme(name: &str) -> (Option<PackageScheme>, &str) {
+    //! Splits a known scheme prefix off of a package/class name, if one is present
+    if let Some(rest) = name.strip_prefix("sys:") {
+        (Some(PackageScheme::Sys), rest)
+    } else if let Some(rest) = name.strip_prefix("local:") {
+        (Some(PackageScheme::Local), rest)
+    } else {
+        (None, name)
+    }
+}
+
+fn group_path(group: &str) -> PathBuf {
+    //! Turns a dotted group like `com.example` into a nested path `com/example`
+    group.split('.').collect()
+}
+
+fn capitalize(s: &str) -> String {
+    //! Uppercases the first character of s, leaving the rest untouched
+    let mut c = s.chars();
+
+    match c.next() {
+        None => String::new(),
+        Some(l) => l.to_uppercase().collect::<String>() + c.as_str(),
+    }
+}
+
+fn uppercamelcase(s: &str) -> String {
+    //! Converts kebab-case, snake_case, or space-separated input into PascalCase
+    s.split(|c: char| c == '-' || c == '_' || c.is_whitespace())
+        .filter(|segment| !segment.is_empty())
+        .map(capitalize)
+        .collect()
+}
+
+fn snakecase(s: &str) -> String {
+    //! Converts kebab-case, PascalCase/camelCase, or space-separated input into snake_case
+    let mut out = String::new();
+    let mut prev_is_lower_or_digit = false;
+
+    for c in s.chars() {
+        if c == '-' || c == '_' || c.is_whitespace() {
+            out.push('_');
+            prev_is_lower_or_digit = false;
+            continue;
+        }
+
+        if c.is_uppercase() {
+            if prev_is_lower_or_digit {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+            prev_is_lower_or_digit = false;
+        } else {
+            out.push(c);
+            prev_is_lower_or_digit = c.is_alphanumeric();
+        }
+    }
+
+    out
+}
+
+// The kind of skeleton `java_line add class --kind <kind>` should generate
+enum TemplateKind {
+    Class,
+    Interface,
+    Enum,
+    Record,
+    Abstract,
+    Test,
+}
+
+impl TemplateKind {
+    fn parse(kind: &str) -> Option<Self> {
+        //! Parses a `--kind` argument, tolerating the casing/separators a user might type
+        match snakecase(kind).as_str() {
+            "class" => Some(TemplateKind::Class),
+            "interface" => Some(TemplateKind::Interface),
+            "enum" => Some(TemplateKind::Enum),
+            "record" => Some(TemplateKind::Record),
+            "abstract" => Some(TemplateKind::Abstract),
+            "test" => Some(TemplateKind::Test),
+            _ => None,
+        }
+    }
+}
+
+fn render_class_template(
+    kind: &TemplateKind,
+    class_name: &str,
+    package_line: Option<&str>,
+) -> String {
+    //! Renders the Java source skeleton for a given template kind, prefixed by package_line if present
+    let body = match kind {
+        TemplateKind::Class => format!(
+            "class {class_name} {{\n\tpublic static void main(String[] args) {{\n\t}}\n}}"
+        ),
+        TemplateKind::Interface => format!("interface {class_name} {{\n}}"),
+        TemplateKind::Enum => format!("enum {class_name} {{\n}}"),
+        TemplateKind::Record => format!("record {class_name}() {{\n}}"),
+        TemplateKind::Abstract => format!("abstract class {class_name} {{\n}}"),
+        TemplateKind::Test => format!(
+            "import org.junit.jupiter.api.Test;\n\nclass {class_name} {{\n\n\t@Test\n\tvoid example() {{\n\t}}\n}}"
+        ),
+    };
+
+    match package_line {
+        Some(package_line) => format!("{package_line}\n\n{body}"),
+        None => body,
+    }
+}
+
+fn load_project_config() -> Result<ProjectConfig, String> {
+    //! Reads and deserializes the `.java_line/project.toml` manifest written by `init`
+    let root = find_root(None).map_err(|e| e.to_string())?;
+
+    let mut manifest =
+        fs::File::open(root.join(".java_line").join("project.toml")).map_err(|e| e.to_string())?;
+
+    let mut buf = String::new();
+    manifest
+        .read_to_string(&mut buf)
+        .map_err(|e| e.to_string())?;
+
+    toml::from_str(&buf).map_err(|e| e.to_string())
+}
+
+fn package_base_dir(
+    root: &Path,
+    config: &ProjectConfig,
+    scheme: Option<&PackageScheme>,
+) -> PathBuf {
+    //! Resolves the directory new packages/classes are rooted under, prepending the
+    //! manifest's `group` and distinguishing vendored (`sys:`) from local package trees
+    match scheme {
+        Some(PackageScheme::Sys) => root.join("vendor").join(group_path(&config.group)),
+        _ => root
+            .join(&config.source_dir)
+            .join(group_path(&config.group)),
+    }
+}
+
 fn init() {
-    //! Initialize the root directory for the java project
+    //! Initialize the root directory for the java project, writing a project.toml manifest
     let thing = fs::DirBuilder::new();
     match thing.create(".java_line") {
         Ok(_) => println!("New root created"),
-        Err(_) => println!("Root already exists"),
-    }
-}
-
-// An option is used here so as to allow recursion within the function, passing in None implies this is the first traversal of directories
-fn find_root(dir: Option<PathBuf>) -> Option<PathBuf> {
-    //! Find the root directory of the current project, must be inside of root directory or one of its children
-    //! Returns the parent directory of the .java_line directory (which marks a directory and its children as a java_line project)
-    match dir {
-        Some(dir_path) => {
-            // This arm is typically taken after one trasversal
-            if let Some(file) = fs::read_dir(dir_path.clone())
-                .unwrap()
-                .map(|e| {
-                    let entry = e.unwrap().path();
-                    fs::canonicalize(entry)
-                })
-                .filter(|e| e.is_ok()) // This arm is used to prevent errors with strange directories that I faced during initial testing
-                .map(|e| {
-                    let binding = e.unwrap();
-                    let entry = binding.as_path();
-                    fs::canonicalize(entry).unwrap()
-                })
-                .find(|e| e.file_name().unwrap().to_str().unwrap() == ".java_line")
-            // This is the actual test to find the .java_line directory
-            {
-                // This branch handles having found the .java_line directory
-                Some(file.parent().unwrap().to_path_buf()) // Return the path to the .java_line directory
-            } else {
-                // This branch handles not having found the .java_line directory within the currently searched directory
-                let parent_dir = dir_path.parent(); // Get the parent directory
-                match parent_dir {
-                    Some(parent) => find_root(Some(parent.to_path_buf())), // If the directory has a parent, pass it in to continue recursion and return the result of that traversal to return at end
-                    None => None,                                          // If there is no parent
-                }
-            }
+        Err(_) => {
+            println!("Root already exists");
+            return;
         }
-        None => {
-            // This arm is typically taken as the first traversal
-            let pwd = env::current_dir().unwrap();
-            if let Some(file) = fs::read_dir(pwd.clone())
-                .unwrap()
-                .map(|e| {
-                    let entry = e.unwrap().path();
-                    fs::canonicalize(entry).unwrap()
-                })
-                .find(|e| e.file_name().unwrap().to_str().unwrap() == ".java_line")
-            {
-                Some(file.parent().unwrap().to_path_buf())
-            } else {
-                find_root(Some(pwd.parent().unwrap().to_path_buf()))
+    }
+
+    let name = env::current_dir()
+        .ok()
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "my-project".to_string());
+
+    let config = ProjectConfig {
+        name,
+        version: "0.1.0".to_string(),
+        group: "com.example".to_string(),
+        source_dir: "src/main/java".to_string(),
+        main_class: "Main".to_string(),
+        exclude: Vec::new(),
+    };
+
+    let toml_content = toml::to_string(&config).unwrap();
+
+    let mut manifest = fs::File::create(".java_line/project.toml").unwrap();
+    manifest.write_all(toml_content.as_bytes()).unwrap();
+}
+
+// Lazily builds and caches the set of child file names for a directory, so repeated
+// marker checks against the same directory cost one read_dir instead of one per check
+struct DirContents {
+    path: PathBuf,
+    names: std::cell::OnceCell<HashSet<String>>,
+}
+
+impl DirContents {
+    fn new(path: PathBuf) -> Self {
+        DirContents {
+            path,
+            names: std::cell::OnceCell::new(),
+        }
+    }
+
+    fn names(&self) -> io::Result<&HashSet<String>> {
+        if let Some(names) = self.names.get() {
+            return Ok(names);
+        }
+
+        let mut names = HashSet::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            if let Some(name) = entry?.file_name().to_str() {
+                names.insert(name.to_string());
             }
         }
+
+        // `names` was confirmed empty above and this struct is never shared across threads
+        Ok(self.names.get_or_init(|| names))
+    }
+
+    fn contains(&self, name: &str) -> io::Result<bool> {
+        Ok(self.names()?.contains(name))
+    }
+}
+
+#[derive(Debug)]
+enum FindRootError {
+    NotAProject,
+    Io(io::Error),
+}
+
+impl std::fmt::Display for FindRootError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FindRootError::NotAProject => write!(f, "{NotJavaLineProject}"),
+            FindRootError::Io(e) => write!(f, "{e}"),
+        }
     }
 }
 
+impl From<io::Error> for FindRootError {
+    fn from(e: io::Error) -> Self {
+        FindRootError::Io(e)
+    }
+}
+
+fn find_root(start: Option<PathBuf>) -> Result<PathBuf, FindRootError> {
+    //! Walks upward from start (or the current directory) looking for a `.java_line` marker directory
+    //! Returns the marker directory's parent, canonicalized once on the final match
+    //! Distinguishes "not inside a project" from IO errors encountered along the way
+    let mut current = Some(match start {
+        Some(dir) => dir,
+        None => env::current_dir()?,
+    });
+
+    while let Some(dir) = current {
+        if DirContents::new(dir.clone()).contains(".java_line")? {
+            return Ok(fs::canonicalize(dir)?);
+        }
+
+        current = dir.parent().map(|p| p.to_path_buf());
+    }
+
+    Err(FindRootError::NotAProject)
+}
+
 fn is_java_line_project() -> bool {
     //! Returns true if the directory is a child of a java_line project or if it is the root directory
     //! Returns false in all other cases
-    match find_root(None) {
-        Some(_) => true,
-        None => false,
-    }
+    find_root(None).is_ok()
 }
 
 fn is_java_line_root_dir() -> bool {
     //! Checks if the pwd is the root directory of the java_line project
+    let cwd = match env::current_dir().and_then(fs::canonicalize) {
+        Ok(cwd) => cwd,
+        Err(_) => return false,
+    };
+
     match find_root(None) {
-        Some(path) => path == env::current_dir().unwrap(),
-        None => false,
+        Ok(root) => root == cwd,
+        Err(_) => false,
     }
 }
 
 fn create_class(
-    class_file_name: &String,
+    class_file_name: &str,
     parent_dir: Option<&String>,
     package_info: Option<String>,
+    kind: &TemplateKind,
 ) {
-    //! Creates the Java file for a new class with name class_file_name
+    //! Creates the Java file for a new class with name class_file_name, rendered from the given template kind
     //! If provided, creates the class within the given parent directory in parent_dir
+    let class_name = uppercamelcase(class_file_name);
 
-    match parent_dir {
-        Some(dir) => {
-            let mut c = class_file_name.chars();
-
-            let class_name = match c.next() {
-                None => String::new(),
-                Some(l) => l.to_uppercase().collect::<String>() + c.as_str(),
-            };
-
-            let mut new_class = fs::File::create(format!("{dir}/{class_name}.java")).unwrap();
-
-            match package_info {
-                Some(info) => {
-                    let file_content = [
-                        &format!("import {info};"),
-                        "\n",
-                        &format!("class {class_name} {{"),
-                        "\n",
-                        "\tpublic static void main(String[] args) {",
-                        "\n",
-                        "\t}",
-                        "\n",
-                        "}",
-                    ];
-
-                    new_class
-                        .write_all(file_content.join("\n").as_bytes())
-                        .unwrap();
-                }
-                None => {
-                    let file_content = [
-                        &format!("class {class_name} {{"),
-                        "\n",
-                        "\tpublic static void main(String[] args) {",
-                        "\n",
-                        "\t}",
-                        "\n",
-                        "}",
-                    ];
-
-                    new_class
-                        .write_all(file_content.join("\n").as_bytes())
-                        .unwrap();
-                }
-            }
-        }
-        None => {
-            // The below solution to capitalizing the first character of class_file_name found on github
-            // https://stackoverflow.com/questions/38406793/why-is-capitalizing-the-first-letter-of-a-string-so-convoluted-in-rust
-            let mut c = class_file_name.chars();
+    let package_line = package_info.map(|info| format!("package {info};"));
 
-            let class_name = match c.next() {
-                None => String::new(),
-                Some(l) => l.to_uppercase().collect::<String>() + c.as_str(),
-            };
+    let content = render_class_template(kind, &class_name, package_line.as_deref());
 
-            let mut new_class = fs::File::create(format!("{class_name}.java")).unwrap();
+    let path = match parent_dir {
+        Some(dir) => format!("{dir}/{class_name}.java"),
+        None => format!("{class_name}.java"),
+    };
 
-            let file_content = [
-                &format!("class {class_name} {{"),
-                "\n",
-                "\tpublic static void main(String[] args) {",
-                "\n",
-                "\t}",
-                "\n",
-                "}",
-            ];
+    let mut new_class = fs::File::create(path).unwrap();
 
-            new_class
-                .write_all(file_content.join("\n").as_bytes())
-                .unwrap();
-        }
-    }
+    new_class.write_all(content.as_bytes()).unwrap();
 }
 
 // java_line add class parent_dir class_name
@@ -187,21 +339,64 @@ fn create_class(
 fn add_class(
     class_file_name: &String,
     parent_dir: Option<&String>,
+    kind: &TemplateKind,
 ) -> Result<(), NotJavaLineProject> {
     //! Creates a new Java class if the user is currently inside of a java_line project
     //! This is a wrapper for create_class, and should be used instead of that class
     if is_java_line_project() {
+        let (scheme, class_file_name) = strip_scheme(class_file_name);
+        let class_file_name = class_file_name.to_string();
+
         match parent_dir {
-            Some(parent) => {
-                let package_info = get_package_info(&parent);
+            // An explicit parent_dir already pins the destination, so a scheme prefix has
+            // nothing left to redirect; it only matters for the no-parent case below, where
+            // it picks between the local source tree and the vendor tree.
+            Some(parent) => match build_package_path(parent) {
+                Ok(package_path) => {
+                    if package_path.is_empty() {
+                        create_class(&class_file_name, Some(parent), None, kind)
+                    } else {
+                        create_class(&class_file_name, Some(parent), Some(package_path), kind);
+                    }
+                }
+                Err(e) => println!("{e}"),
+            },
+            None => match load_project_config() {
+                Ok(config) => {
+                    let root = match find_root(None) {
+                        Ok(root) => root,
+                        Err(e) => {
+                            println!("{e}");
+                            return Ok(());
+                        }
+                    };
+                    let base = package_base_dir(&root, &config, scheme.as_ref());
+
+                    if let Err(e) = fs::create_dir_all(&base) {
+                        println!("{e}");
+                        return Ok(());
+                    }
 
-                if package_info.is_empty() {
-                    create_class(class_file_name, Some(parent), None)
-                } else {
-                    create_class(class_file_name, Some(parent), Some(package_info));
+                    let base = base.to_string_lossy().to_string();
+
+                    match build_package_path(&base) {
+                        Ok(package_path) => {
+                            if package_path.is_empty() {
+                                create_class(&class_file_name, Some(&base), None, kind)
+                            } else {
+                                create_class(
+                                    &class_file_name,
+                                    Some(&base),
+                                    Some(package_path),
+                                    kind,
+                                );
+                            }
+                        }
+                        Err(e) => println!("{e}"),
+                    }
                 }
-            }
-            None => create_class(class_file_name, None, None),
+                Err(_) => create_class(&class_file_name, None, None, kind),
+            },
         }
         Ok(())
     } else {
@@ -210,41 +405,540 @@ fn add_class(
 }
 
 fn new_package(package_name: &String) {
-    if is_java_line_project() {
-        let new_pack = fs::DirBuilder::new();
+    if !is_java_line_project() {
+        println!("You are not in a java_line project");
+        return;
+    }
+
+    let (scheme, name) = strip_scheme(package_name);
+
+    let name = match validate_package_segment(name) {
+        Ok(name) => name,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    let config = match load_project_config() {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    let root = match find_root(None) {
+        Ok(root) => root,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+    let base = package_base_dir(&root, &config, scheme.as_ref());
+
+    if let Err(e) = fs::create_dir_all(&base) {
+        println!("{e}");
+        return;
+    }
+
+    let pack_dir = base.join(&name);
+
+    let new_pack = fs::DirBuilder::new();
+
+    match new_pack.create(&pack_dir) {
+        Ok(_) => (),
+        Err(_) => {
+            println!("Package already exists");
+            return;
+        }
+    }
+
+    let mut pack_decl = fs::File::create(pack_dir.join("pack_def.toml")).unwrap();
+
+    let pack_decl_content = format!("name=\"{name}\"");
+
+    pack_decl.write_all(pack_decl_content.as_bytes()).unwrap();
+}
+
+const JAVA_RESERVED_WORDS: [&str; 53] = [
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "true",
+    "false",
+    "null",
+];
+
+fn validate_package_segment(segment: &str) -> Result<String, String> {
+    //! Validates a single dotted package segment against Java identifier rules:
+    //! no leading digit, no hyphens, and not a reserved word
+    if segment.is_empty() {
+        return Err("Package segment cannot be empty".to_string());
+    }
+
+    if segment.contains('-') {
+        return Err(format!(
+            "Package segment \"{segment}\" cannot contain hyphens"
+        ));
+    }
+
+    let mut chars = segment.chars();
+    let first = chars.next().unwrap();
+
+    if !(first.is_alphabetic() || first == '_' || first == '$') {
+        return Err(format!(
+            "Package segment \"{segment}\" is not a valid Java identifier"
+        ));
+    }
 
-        match new_pack.create(&package_name) {
-            Ok(_) => (),
-            Err(_) => {
-                println!("Package already exists");
-                return;
+    if !chars
+        .clone()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    {
+        return Err(format!(
+            "Package segment \"{segment}\" is not a valid Java identifier"
+        ));
+    }
+
+    if JAVA_RESERVED_WORDS.contains(&segment) {
+        return Err(format!(
+            "Package segment \"{segment}\" is a reserved Java keyword"
+        ));
+    }
+
+    Ok(segment.to_string())
+}
+
+fn package_root() -> Result<PathBuf, String> {
+    //! Resolves the directory package paths are computed relative to: the manifest's
+    //! source_dir when a project.toml exists, otherwise the bare project root
+    let root = find_root(None).map_err(|e| e.to_string())?;
+
+    match load_project_config() {
+        Ok(config) => Ok(root.join(&config.source_dir)),
+        Err(_) => Ok(root),
+    }
+}
+
+fn build_package_path(parent_dir: &String) -> Result<String, String> {
+    //! Walks from parent_dir up to the package root (or the project's `vendor` directory), concatenating each ancestor's package name
+    //! (from pack_def.toml, falling back to the directory name) into a dotted package path
+    let root = package_root()?;
+    let vendor_root = find_root(None).map_err(|e| e.to_string())?.join("vendor");
+    let parent_path = fs::canonicalize(parent_dir).map_err(|e| e.to_string())?;
+
+    let mut segments = Vec::new();
+    let mut current = Some(parent_path.as_path());
+
+    while let Some(dir) = current {
+        if dir == root || dir == vendor_root {
+            break;
+        }
+
+        let segment = match fs::File::open(dir.join("pack_def.toml")) {
+            Ok(mut pack_def) => {
+                let mut buf = String::new();
+                pack_def
+                    .read_to_string(&mut buf)
+                    .map_err(|e| e.to_string())?;
+                let info: JavaLineConfig = toml::from_str(&buf).map_err(|e| e.to_string())?;
+                info.name
             }
+            Err(_) => dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_string(),
+        };
+
+        segments.push(validate_package_segment(&segment)?);
+
+        current = dir.parent();
+    }
+
+    segments.reverse();
+
+    Ok(segments.join("."))
+}
+
+fn find_jdk_home() -> Option<PathBuf> {
+    //! Locates the JDK installation directory
+    //! Checks JAVA_HOME first, then falls back to probing common install roots and the directory containing `javac` on PATH
+    if let Ok(java_home) = env::var("JAVA_HOME") {
+        let path = PathBuf::from(java_home);
+        if path.join("bin").join("javac").exists() {
+            return Some(path);
         }
+    }
 
-        let mut pack_decl = fs::File::create(package_name.clone() + "/pack_def.toml").unwrap();
+    let common_roots = [
+        "/usr/lib/jvm",
+        "/usr/lib64/jvm",
+        "/opt/jdk",
+        "/Library/Java/JavaVirtualMachines",
+    ];
 
-        let pack_decl_content = format!("name=\"{}\"", package_name);
+    for root in common_roots.iter() {
+        if let Ok(entries) = fs::read_dir(root) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if path.join("bin").join("javac").exists() {
+                    return Some(path);
+                }
+                if path.join("Contents/Home/bin/javac").exists() {
+                    return Some(path.join("Contents/Home"));
+                }
+            }
+        }
+    }
 
-        pack_decl.write(pack_decl_content.as_bytes()).unwrap();
-    } else {
-        println!("You are not in a java_line project");
+    if let Ok(path_var) = env::var("PATH") {
+        for dir in env::split_paths(&path_var) {
+            if dir.join("javac").exists() {
+                return dir.parent().map(|p| p.to_path_buf());
+            }
+        }
+    }
+
+    None
+}
+
+fn collect_java_files(dir: &PathBuf, files: &mut Vec<PathBuf>) {
+    //! Recursively collects every `.java` file found under dir
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                collect_java_files(&path, files);
+            } else if path.extension().is_some_and(|ext| ext == "java") {
+                files.push(path);
+            }
+        }
     }
 }
 
-fn get_package_info(target_dir: &String) -> String {
-    // target_dir will be in form of path/to/dest, there should NOT be a / after dest
-    let mut pack_def = match fs::File::open(format!("{target_dir}/pack_def.toml")) {
-        Ok(tf) => tf,
-        Err(_) => return "".to_string(),
+fn build() -> Result<(), String> {
+    //! Compiles every `.java` file under the project root into `target/classes`
+    //! Surfaces javac's exit status and stderr instead of panicking, since compilation errors are expected output
+    let root = find_root(None).map_err(|e| e.to_string())?;
+
+    let jdk_home = find_jdk_home()
+        .ok_or_else(|| "Could not locate a JDK, set JAVA_HOME or add javac to PATH".to_string())?;
+
+    let mut sources = Vec::new();
+    collect_java_files(&root, &mut sources);
+
+    if sources.is_empty() {
+        return Err("No .java files found to compile".to_string());
+    }
+
+    let out_dir = root.join("target").join("classes");
+    fs::create_dir_all(&out_dir).map_err(|e| e.to_string())?;
+
+    let output = process::Command::new(jdk_home.join("bin").join("javac"))
+        .arg("-d")
+        .arg(&out_dir)
+        .args(&sources)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(())
+}
+
+fn run(class_name: &String) -> Result<(), String> {
+    //! Builds the project, then runs the resulting class with `java -cp target/classes <Class>`
+    //! Only the final `.`-segment is normalized to PascalCase the same way `create_class` does;
+    //! package segments are passed through unchanged since most classes land under the manifest's group
+    build()?;
+
+    let root = find_root(None).map_err(|e| e.to_string())?;
+
+    let jdk_home = find_jdk_home()
+        .ok_or_else(|| "Could not locate a JDK, set JAVA_HOME or add javac to PATH".to_string())?;
+
+    let class_name = match class_name.rsplit_once('.') {
+        Some((package, name)) => format!("{package}.{}", uppercamelcase(name)),
+        None => uppercamelcase(class_name),
     };
 
-    let mut buf = String::new();
+    let status = process::Command::new(jdk_home.join("bin").join("java"))
+        .arg("-cp")
+        .arg(root.join("target").join("classes"))
+        .arg(&class_name)
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if !status.success() {
+        return Err(format!("java exited with status {status}"));
+    }
+
+    Ok(())
+}
+
+fn glob_segment_match(pattern: &[u8], text: &[u8]) -> bool {
+    //! Matches a single path segment against a pattern containing `*` wildcards
+    match (pattern.first(), text.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob_segment_match(&pattern[1..], text)
+                || (!text.is_empty() && glob_segment_match(pattern, &text[1..]))
+        }
+        (Some(p), Some(t)) if p == t => glob_segment_match(&pattern[1..], &text[1..]),
+        _ => false,
+    }
+}
+
+fn glob_match(pattern: &str, candidate: &str) -> bool {
+    //! Matches a `/`-separated path against a glob pattern supporting `*` (within a segment)
+    //! and `**` (across any number of segments), the way package tooling excludes files
+    fn match_segments(pattern: &[&str], candidate: &[&str]) -> bool {
+        match pattern.first() {
+            None => candidate.is_empty(),
+            Some(&"**") => {
+                match_segments(&pattern[1..], candidate)
+                    || matches!(candidate.split_first(), Some((_, rest)) if match_segments(pattern, rest))
+            }
+            Some(seg) => match candidate.split_first() {
+                Some((first, rest)) if glob_segment_match(seg.as_bytes(), first.as_bytes()) => {
+                    match_segments(&pattern[1..], rest)
+                }
+                _ => false,
+            },
+        }
+    }
+
+    let pattern_segs: Vec<&str> = pattern.split('/').collect();
+    let candidate_segs: Vec<&str> = candidate.split('/').collect();
+
+    match_segments(&pattern_segs, &candidate_segs)
+}
 
-    pack_def.read_to_string(&mut buf).unwrap();
+fn load_exclude_patterns(root: &Path, config: &ProjectConfig) -> Result<Vec<String>, String> {
+    //! Combines the manifest's `exclude` globs with any patterns found in the project's `.gitignore`
+    let mut patterns = config.exclude.clone();
 
-    let info: JavaLineConfig = toml::from_str(buf.as_str()).unwrap();
+    if let Ok(mut gitignore) = fs::File::open(root.join(".gitignore")) {
+        let mut buf = String::new();
+        gitignore
+            .read_to_string(&mut buf)
+            .map_err(|e| e.to_string())?;
 
-    info.name
+        for line in buf.lines() {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                patterns.push(line.to_string());
+            }
+        }
+    }
+
+    Ok(patterns)
+}
+
+fn is_excluded(rel_path: &Path, exclude: &[String]) -> bool {
+    let rel_str = rel_path.to_string_lossy().replace('\\', "/");
+
+    exclude.iter().any(|pattern| {
+        // A trailing `/` marks a directory-only pattern (e.g. `target/`); the distinction
+        // between files and directories isn't tracked here, so match it like any other name
+        let pattern = pattern.strip_suffix('/').unwrap_or(pattern);
+
+        // A leading `/` root-anchors the pattern instead of leaving an empty first segment
+        let pattern = if let Some(anchored) = pattern.strip_prefix('/') {
+            anchored.to_string()
+        } else if pattern.contains('/') {
+            // A pattern with an interior `/` is already anchored to the root, mirroring .gitignore semantics
+            pattern.to_string()
+        } else {
+            // A pattern with no `/` matches at any depth, mirroring .gitignore semantics
+            format!("**/{pattern}")
+        };
+
+        // A pattern that matches a directory also excludes everything beneath it
+        glob_match(&format!("{pattern}/**"), &rel_str)
+    })
+}
+
+fn collect_resources(dir: &Path, base: &Path, exclude: &[String], out: &mut Vec<PathBuf>) {
+    //! Recursively collects non-`.java`, non-manifest resource files under dir, honoring exclude globs
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let rel = path.strip_prefix(base).unwrap().to_path_buf();
+
+            if is_excluded(&rel, exclude) {
+                continue;
+            }
+
+            if path.is_dir() {
+                collect_resources(&path, base, exclude, out);
+            } else if path.extension().is_none_or(|ext| ext != "java")
+                && rel.file_name().is_none_or(|name| name != "pack_def.toml")
+            {
+                out.push(rel);
+            }
+        }
+    }
+}
+
+fn collect_relative_files(dir: &Path, base: &Path, exclude: &[String], out: &mut Vec<PathBuf>) {
+    //! Recursively collects every file under dir, relative to base, honoring exclude globs
+    //! `target/classes` mirrors the source tree's package layout, so the same source-relative
+    //! exclude patterns apply here too (e.g. `**/scratch/**` still matches the compiled output)
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let rel = path.strip_prefix(base).unwrap().to_path_buf();
+
+            if is_excluded(&rel, exclude) {
+                continue;
+            }
+
+            if path.is_dir() {
+                collect_relative_files(&path, base, exclude, out);
+            } else {
+                out.push(rel);
+            }
+        }
+    }
+}
+
+fn package() -> Result<(), String> {
+    //! Builds the project, then bundles `target/classes` plus any source-tree resources into a distributable JAR
+    //! Honors the manifest's `exclude` globs and the project's `.gitignore`, and prints what shipped
+    build()?;
+
+    let root = find_root(None).map_err(|e| e.to_string())?;
+    let config = load_project_config()?;
+    let exclude = load_exclude_patterns(&root, &config)?;
+
+    let classes_dir = root.join("target").join("classes");
+    let source_root = root.join(&config.source_dir);
+
+    let mut resources = Vec::new();
+    collect_resources(&source_root, &source_root, &exclude, &mut resources);
+
+    for rel in &resources {
+        let dest = classes_dir.join(rel);
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        fs::copy(source_root.join(rel), &dest).map_err(|e| e.to_string())?;
+    }
+
+    let mut included = Vec::new();
+    collect_relative_files(&classes_dir, &classes_dir, &exclude, &mut included);
+
+    if included.is_empty() {
+        return Err("No files found to package".to_string());
+    }
+
+    let jdk_home = find_jdk_home()
+        .ok_or_else(|| "Could not locate a JDK, set JAVA_HOME or add javac to PATH".to_string())?;
+
+    let jar_path = root
+        .join("target")
+        .join(format!("{}-{}.jar", config.name, config.version));
+
+    let mut jar_cmd = process::Command::new(jdk_home.join("bin").join("jar"));
+
+    jar_cmd
+        .arg("--create")
+        .arg("--file")
+        .arg(&jar_path)
+        .arg("--main-class")
+        .arg(&config.main_class);
+
+    // jar only honors a file following the -C that immediately precedes it, so each
+    // included file needs its own -C <classes_dir> <file> triple
+    for file in &included {
+        jar_cmd.arg("-C").arg(&classes_dir).arg(file);
+    }
+
+    let output = jar_cmd.output().map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    println!("Included files:");
+    for file in &included {
+        println!("  {}", file.display());
+    }
+
+    println!("Wrote {}", jar_path.display());
+
+    Ok(())
+}
+
+fn extract_flag_value(args: &[String], flag: &str) -> (Vec<String>, Option<String>) {
+    //! Pulls a `--flag value` pair out of argv, returning the remaining positional args and the captured value
+    let mut remaining = Vec::new();
+    let mut value = None;
+    let mut iter = args.iter().cloned();
+
+    while let Some(arg) = iter.next() {
+        if arg == flag {
+            value = iter.next();
+        } else {
+            remaining.push(arg);
+        }
+    }
+
+    (remaining, value)
 }
 
 fn main() {
@@ -258,12 +952,18 @@ fn main() {
         // Class
         if args[2] == "class" {
             // Add a class
-            if args.len() > 4 {
+            let (positional, kind) = extract_flag_value(&args[3..], "--kind");
+            let kind = kind
+                .as_deref()
+                .and_then(TemplateKind::parse)
+                .unwrap_or(TemplateKind::Class);
+
+            if positional.len() > 1 {
                 // With source directory specified
-                add_class(&args[3], Some(&args[4])).unwrap();
+                add_class(&positional[0], Some(&positional[1]), &kind).unwrap();
             } else {
                 // Without source directory specified
-                add_class(&args[3], None).unwrap();
+                add_class(&positional[0], None, &kind).unwrap();
             }
         }
         // Package
@@ -271,5 +971,166 @@ fn main() {
             // Add a package
             new_package(&args[3]);
         }
+    } else if args[1] == "build" {
+        // Build branch
+        if let Err(e) = build() {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    } else if args[1] == "run" {
+        // Run branch
+        if args.len() < 3 {
+            eprintln!("Usage: java_line run <class_name>");
+            process::exit(1);
+        }
+        if let Err(e) = run(&args[2]) {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    } else if args[1] == "package" {
+        // Package branch
+        if let Err(e) = package() {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // A fresh directory per test under the system temp dir, so filesystem-backed tests
+    // don't collide with each other or with a real project the test happens to run inside
+    fn unique_temp_dir(label: &str) -> PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = env::temp_dir().join(format!("java_line_test_{}_{label}_{n}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn glob_match_matches_star_within_a_segment() {
+        assert!(glob_match("*.txt", "notes.txt"));
+        assert!(!glob_match("*.txt", "notes.java"));
+    }
+
+    #[test]
+    fn glob_match_matches_double_star_across_segments() {
+        assert!(glob_match("**/scratch/**", "com/example/scratch/Temp.class"));
+        assert!(!glob_match("**/scratch/**", "com/example/Temp.class"));
+    }
+
+    #[test]
+    fn is_excluded_matches_directory_only_patterns() {
+        let exclude = vec!["target/".to_string()];
+        assert!(is_excluded(Path::new("target/classes/Foo.class"), &exclude));
+    }
+
+    #[test]
+    fn is_excluded_matches_root_anchored_patterns() {
+        let exclude = vec!["/test_output.txt".to_string()];
+        assert!(is_excluded(Path::new("test_output.txt"), &exclude));
+        assert!(!is_excluded(
+            Path::new("nested/test_output.txt"),
+            &exclude
+        ));
+    }
+
+    #[test]
+    fn is_excluded_matches_bare_names_at_any_depth() {
+        let exclude = vec!["scratch".to_string()];
+        assert!(is_excluded(Path::new("com/example/scratch"), &exclude));
+    }
+
+    #[test]
+    fn find_root_locates_marker_directory_upward() {
+        let root = unique_temp_dir("find_root_ok");
+        fs::create_dir(root.join(".java_line")).unwrap();
+        let nested = root.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+
+        let found = find_root(Some(nested)).unwrap();
+        assert_eq!(found, fs::canonicalize(&root).unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn find_root_errors_outside_a_project() {
+        let dir = unique_temp_dir("find_root_missing");
+        assert!(matches!(
+            find_root(Some(dir.clone())),
+            Err(FindRootError::NotAProject)
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dir_contents_contains_checks_file_names() {
+        let dir = unique_temp_dir("dir_contents");
+        fs::write(dir.join("Foo.java"), b"").unwrap();
+
+        let contents = DirContents::new(dir.clone());
+        assert!(contents.contains("Foo.java").unwrap());
+        assert!(!contents.contains("Bar.java").unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn capitalize_uppercases_only_the_first_character() {
+        assert_eq!(capitalize("hello"), "Hello");
+        assert_eq!(capitalize("Hello"), "Hello");
+        assert_eq!(capitalize(""), "");
+    }
+
+    #[test]
+    fn uppercamelcase_joins_segments_from_any_separator() {
+        assert_eq!(uppercamelcase("hello-world"), "HelloWorld");
+        assert_eq!(uppercamelcase("hello_world"), "HelloWorld");
+        assert_eq!(uppercamelcase("hello world"), "HelloWorld");
+        assert_eq!(uppercamelcase("HelloWorld"), "HelloWorld");
+    }
+
+    #[test]
+    fn uppercamelcase_does_not_split_on_dots() {
+        // Dotted class paths are split by callers, which normalize only the final segment
+        assert_eq!(uppercamelcase("com.example.hello"), "Com.example.hello");
+    }
+
+    #[test]
+    fn snakecase_joins_segments_with_underscores() {
+        assert_eq!(snakecase("HelloWorld"), "hello_world");
+        assert_eq!(snakecase("hello-world"), "hello_world");
+        assert_eq!(snakecase("hello world"), "hello_world");
+    }
+
+    #[test]
+    fn validate_package_segment_accepts_valid_identifiers() {
+        assert_eq!(validate_package_segment("example").unwrap(), "example");
+        assert_eq!(validate_package_segment("_foo").unwrap(), "_foo");
+    }
+
+    #[test]
+    fn validate_package_segment_rejects_empty() {
+        assert!(validate_package_segment("").is_err());
+    }
+
+    #[test]
+    fn validate_package_segment_rejects_hyphens() {
+        assert!(validate_package_segment("my-package").is_err());
+    }
+
+    #[test]
+    fn validate_package_segment_rejects_reserved_words() {
+        assert!(validate_package_segment("class").is_err());
+    }
+
+    #[test]
+    fn validate_package_segment_rejects_leading_digit() {
+        assert!(validate_package_segment("1foo").is_err());
     }
 }